@@ -0,0 +1,47 @@
+use std::thread;
+use std::time::Duration;
+
+use serde::Serialize;
+
+/// One status block per the i3bar/swaybar protocol
+/// (<https://i3wm.org/docs/i3bar-protocol.html>).
+#[derive(Serialize, Clone, Debug, Default)]
+pub struct Block {
+    pub full_text: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub short_text: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    pub name: String,
+    pub instance: String,
+}
+
+impl Block {
+    pub fn new(name: &str, instance: &str, full_text: String) -> Self {
+        Self {
+            full_text,
+            name: name.to_string(),
+            instance: instance.to_string(),
+            ..Default::default()
+        }
+    }
+}
+
+/// Prints the protocol header: the `{"version":1}` line followed by the
+/// opening `[` of the infinite block-array stream.
+pub fn print_header() {
+    println!("{{\"version\":1}}");
+    println!("[");
+}
+
+/// Drives the infinite block-array stream: calls `make_blocks` every
+/// `interval` and prints its result as one protocol line (`[block, ...],`).
+pub fn stream<F: FnMut() -> Vec<Block>>(interval: Duration, mut make_blocks: F) -> ! {
+    print_header();
+    loop {
+        let blocks = make_blocks();
+        let json = serde_json::to_string(&blocks).unwrap_or_else(|_| "[]".to_string());
+        println!("{},", json);
+        thread::sleep(interval);
+    }
+}
@@ -1,7 +1,8 @@
 use std::{
     collections::HashMap,
     error::Error,
-    sync::Arc,
+    path::PathBuf,
+    sync::{Arc, Mutex},
     time::{Duration, Instant},
 };
 use tokio::{
@@ -11,16 +12,137 @@ use tokio::{
 
 // --- 模块导入 ---
 mod modules;
+mod network;
 use modules::{
     battery::BatteryInfo,
-    cpu::{CpuLoad, CpuTemp},
+    config::Config,
+    cpu::{CpuCoreMonitor, CpuLoad, CpuTemp},
+    disk::DiskInfo,
+    load::LoadAvg,
     memory::MemoryInfo,
     network::NetworkStats,
+    process::{ProcessMonitor, TopProcess},
     time::current_time as get_current_time,
 };
 
+/// Parses `-c`/`--config <path>` out of argv, falling back to
+/// [`Config::default_path`] when it isn't given.
+fn config_path_from_args() -> PathBuf {
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == "-c" || arg == "--config" {
+            if let Some(path) = args.next() {
+                return PathBuf::from(path);
+            }
+        }
+    }
+    Config::default_path()
+}
+
+/// Parses `--network-config <path>` out of argv, falling back to
+/// [`network::NetConfig::default_path`] when it isn't given. Mirrors
+/// [`config_path_from_args`] for the standalone legacy network module, which
+/// has its own TOML file separate from the main `config.toml`.
+fn network_config_path_from_args() -> PathBuf {
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == "--network-config" {
+            if let Some(path) = args.next() {
+                return PathBuf::from(path);
+            }
+        }
+    }
+    network::NetConfig::default_path()
+}
+
+/// Which legacy network engine feeds `{net_legacy}`. `None` (the default)
+/// means the legacy module is disabled entirely, so it doesn't run a second,
+/// always-on network engine duplicating `modules::network`'s
+/// `{net_rx}`/`{net_tx}` unless the user opts in.
+#[derive(Clone, Copy, PartialEq)]
+enum LegacyNetMode {
+    /// The background-thread sampler, falling back to
+    /// [`network::print_network_speed`] whenever the sampler's latest rate is
+    /// zero in both directions: before its first sample (e.g. right after
+    /// startup) and also during any interval with no traffic at all.
+    Raw,
+    /// [`network::print_network_speed`]'s EWMA-smoothed total.
+    Ewma,
+    /// [`network::print_network_speed_per_iface`], optionally narrowed to
+    /// one interface via `--legacy-net-iface`.
+    PerIface,
+}
+
+/// Parses `--legacy-net <raw|ewma|per-iface>` out of argv. Absent means
+/// [`LegacyNetMode`] stays `None` and the legacy module never runs.
+fn legacy_net_mode_from_args() -> Option<LegacyNetMode> {
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == "--legacy-net" {
+            return Some(match args.next().as_deref() {
+                Some("raw") => LegacyNetMode::Raw,
+                Some("per-iface") => LegacyNetMode::PerIface,
+                _ => LegacyNetMode::Ewma,
+            });
+        }
+    }
+    None
+}
+
+/// Parses `--legacy-net-iface <name>` out of argv, used by
+/// [`LegacyNetMode::PerIface`] to show one interface instead of every
+/// tracked interface.
+fn legacy_net_iface_from_args() -> Option<String> {
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == "--legacy-net-iface" {
+            return args.next();
+        }
+    }
+    None
+}
+
+/// Renders the configured format string, substituting `{placeholder}` tokens
+/// for the computed value of each module.
+fn render_format(template: &str, values: &HashMap<&str, String>) -> String {
+    let mut out = template.to_string();
+    for (key, val) in values {
+        out = out.replace(&format!("{{{}}}", key), val);
+    }
+    out
+}
+
+/// `color` defaults to off in the `NetConfig` TOML schema so plain-text bars
+/// aren't corrupted by ANSI escapes; `--legacy-net-color` opts a
+/// terminal/shell caller in without requiring a TOML file on disk. Shared by
+/// every legacy network entry point so they can't drift out of sync on how
+/// color selection works.
+fn apply_legacy_net_color_override(mut config: network::NetConfig) -> network::NetConfig {
+    if std::env::args().any(|arg| arg == "--legacy-net-color") {
+        config.color = true;
+    }
+    config
+}
+
+/// Standalone legacy network i3bar/swaybar stream (`--legacy-i3bar`), as an
+/// alternative to the normal multi-module `Scheduler` output below. Exists so
+/// `src/network.rs`'s `Block`/`i3bar`-based output path has a real caller.
+/// Shares `--legacy-net-color`/`--legacy-net-iface` with the
+/// scheduler-embedded `--legacy-net` modes so the two entry points can't
+/// drift out of sync on how color/interface selection works.
+fn run_legacy_i3bar() -> ! {
+    let config = apply_legacy_net_color_override(network::NetConfig::load(&network_config_path_from_args()));
+    network::run_i3bar_stream(config, legacy_net_iface_from_args(), Duration::from_secs(2))
+}
+
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn Error>> {
+    if std::env::args().any(|arg| arg == "--legacy-i3bar") {
+        run_legacy_i3bar();
+    }
+
+    let config = Config::load(&config_path_from_args());
+
     // ---- 1. 初始化 Channels 和 Notifier ----
     let (bat_tx, mut bat_rx) = watch::channel(BatteryInfo::default());
     let (cpu_load_tx, mut cpu_load_rx) = watch::channel(0.0);
@@ -28,41 +150,71 @@ async fn main() -> Result<(), Box<dyn Error>> {
     let (cpu_temp_tx, mut cpu_temp_rx) = watch::channel(CpuTemp::default());
     let (net_tx, mut net_rx) = watch::channel((0.0, 0.0)); // (rx, tx)
     let (time_tx, mut time_rx) = watch::channel(get_current_time());
+    let (disk_tx, mut disk_rx) = watch::channel(DiskInfo::default());
+    let (cpu_bar_tx, mut cpu_bar_rx) = watch::channel(String::new());
+    let (load_tx, mut load_rx) = watch::channel(LoadAvg::default());
+    let (proc_tx, mut proc_rx) = watch::channel(TopProcess::default());
+    // Standalone legacy network subsystem (src/network.rs), independent of
+    // modules::network's `{net_rx}`/`{net_tx}` figures above.
+    let (legacy_net_tx, mut legacy_net_rx) = watch::channel(String::new());
 
     let notify = Arc::new(Notify::new());
 
     // ---- 2. 打印任务 ----
     let print_notify = notify.clone();
+    let print_config = config.clone();
     tokio::spawn(async move {
         loop {
             print_notify.notified().await;
-            
+
             let bat = bat_rx.borrow();
             let cpu = *cpu_load_rx.borrow();
             let mem = mem_rx.borrow();
             let temp = cpu_temp_rx.borrow();
             let net = *net_rx.borrow();
             let time = time_rx.borrow();
+            let disk = disk_rx.borrow();
+            let cpu_bar = cpu_bar_rx.borrow();
+            let load = load_rx.borrow();
+            let top_proc = proc_rx.borrow();
 
             let temp_str = if temp.celsius < 0.0 {
                 "N/A".to_string()
             } else {
                 format!("{:.1}°C", temp.celsius)
             };
-            
-            println!(
-                "Mem: {:.1}G | Net: ↓{:.1}M/s ↑{:.1}M/s | CPU: {:.1}% {} | {} | {}",
-                mem.available_mb(),
-                net.0, net.1,
-                cpu, temp_str,
-                *bat, *time
-            );
+
+            let disk_str = match disk.root() {
+                Some(mount) => format!(
+                    "{:.1}G free ({:.0}%)",
+                    disk.available_gb(mount).unwrap_or(0.0),
+                    disk.used_percent(mount).unwrap_or(0.0)
+                ),
+                None => "N/A".to_string(),
+            };
+
+            let mut values = HashMap::new();
+            values.insert("mem", format!("{:.1}G", mem.available_gb()));
+            values.insert("disk", disk_str);
+            values.insert("net_rx", format!("{:.1}", net.0));
+            values.insert("net_tx", format!("{:.1}", net.1));
+            values.insert("cpu", format!("{:.1}%", cpu));
+            values.insert("cpu_bar", cpu_bar.clone());
+            values.insert("load", format!("{}", *load));
+            values.insert("proc", format!("{} {:.0}%", top_proc.name, top_proc.cpu_percent));
+            values.insert("temp", temp_str);
+            values.insert("bat", format!("{}", *bat));
+            values.insert("time", format!("{}", *time));
+            values.insert("net_legacy", legacy_net_rx.borrow().clone());
+
+            println!("{}", render_format(&print_config.format, &values));
         }
     });
 
     // ---- 3. 调度器任务 ----
     let scheduler = Scheduler::new(
-        bat_tx, cpu_load_tx, mem_tx, cpu_temp_tx, net_tx, time_tx, notify,
+        bat_tx, cpu_load_tx, mem_tx, cpu_temp_tx, net_tx, time_tx, disk_tx, cpu_bar_tx, load_tx,
+        proc_tx, legacy_net_tx, notify, config,
     )?;
     scheduler.run().await;
 
@@ -78,8 +230,36 @@ struct Scheduler {
     cpu_temp_tx: watch::Sender<CpuTemp>,
     net_tx: watch::Sender<(f64, f64)>,
     time_tx: watch::Sender<String>,
-    cpu_monitor: CpuLoad,
-    net_monitor: NetworkStats,
+    disk_tx: watch::Sender<DiskInfo>,
+    cpu_bar_tx: watch::Sender<String>,
+    load_tx: watch::Sender<LoadAvg>,
+    proc_tx: watch::Sender<TopProcess>,
+    legacy_net_tx: watch::Sender<String>,
+    // `None` disables the whole legacy module (the default): no config file
+    // is loaded and no sampler thread is started, so it doesn't duplicate
+    // modules::network's polling unless the user explicitly opts in.
+    legacy_net_mode: Option<LegacyNetMode>,
+    legacy_net_iface: Option<String>,
+    // Only constructed for LegacyNetMode::Raw, which is the only mode that
+    // needs the background-thread sampler rather than a one-shot read.
+    legacy_net_sampler: Option<network::NetSampler>,
+    legacy_net_config: Option<network::NetConfig>,
+    // Same reasoning as cpu_core_monitor/process_monitor/net_monitor below:
+    // prev_idle/prev_total must carry forward from the previous tick, not
+    // restart from CpuLoad::new()'s baseline every time.
+    cpu_monitor: Arc<Mutex<CpuLoad>>,
+    // Same reasoning as cpu_core_monitor/process_monitor: per-interface
+    // counters must advance from the previous tick's sample, not restart
+    // from the counters read at Scheduler::new().
+    net_monitor: Arc<Mutex<NetworkStats>>,
+    // Shared rather than cloned-per-tick: CpuCoreMonitor's sliding window
+    // must advance across ticks, and a clone mutated inside spawn_blocking
+    // and then dropped would never move the baseline forward.
+    cpu_core_monitor: Arc<Mutex<CpuCoreMonitor>>,
+    // Same reasoning as cpu_core_monitor: prev_ticks/prev_total must carry
+    // forward from the previous tick, not restart from ProcessMonitor::new().
+    process_monitor: Arc<Mutex<ProcessMonitor>>,
+    config: Config,
 }
 
 impl Scheduler {
@@ -90,13 +270,44 @@ impl Scheduler {
         cpu_temp_tx: watch::Sender<CpuTemp>,
         net_tx: watch::Sender<(f64, f64)>,
         time_tx: watch::Sender<String>,
+        disk_tx: watch::Sender<DiskInfo>,
+        cpu_bar_tx: watch::Sender<String>,
+        load_tx: watch::Sender<LoadAvg>,
+        proc_tx: watch::Sender<TopProcess>,
+        legacy_net_tx: watch::Sender<String>,
         notify: Arc<Notify>,
+        config: Config,
     ) -> Result<Self, Box<dyn Error>> {
+        let legacy_net_mode = legacy_net_mode_from_args();
+        let legacy_net_iface = legacy_net_iface_from_args();
+
+        // Only load the TOML file (and apply the color override) when a mode
+        // was actually selected, so an unconfigured legacy network.toml isn't
+        // read on every startup for a feature nobody asked for.
+        let legacy_net_config = legacy_net_mode.map(|_| {
+            apply_legacy_net_color_override(network::NetConfig::load(&network_config_path_from_args()))
+        });
+
+        let legacy_net_sampler = match (legacy_net_mode, &legacy_net_config) {
+            (Some(LegacyNetMode::Raw), Some(cfg)) => {
+                Some(network::NetSampler::start(cfg.clone(), Duration::from_secs(2)))
+            }
+            _ => None,
+        };
+
         Ok(Self {
             last_run: HashMap::new(),
-            notify, bat_tx, cpu_load_tx, mem_tx, cpu_temp_tx, net_tx, time_tx,
-            cpu_monitor: CpuLoad::new()?,
-            net_monitor: NetworkStats::new(),
+            notify, bat_tx, cpu_load_tx, mem_tx, cpu_temp_tx, net_tx, time_tx, disk_tx, cpu_bar_tx,
+            load_tx, proc_tx, legacy_net_tx,
+            legacy_net_mode, legacy_net_iface, legacy_net_sampler, legacy_net_config,
+            cpu_monitor: Arc::new(Mutex::new(CpuLoad::new()?)),
+            net_monitor: Arc::new(Mutex::new(NetworkStats::with_filters(
+                config.network.include.clone(),
+                config.network.exclude.clone(),
+            ))),
+            cpu_core_monitor: Arc::new(Mutex::new(CpuCoreMonitor::new())),
+            process_monitor: Arc::new(Mutex::new(ProcessMonitor::new())),
+            config,
         })
     }
 
@@ -107,7 +318,7 @@ impl Scheduler {
             ticker.tick().await;
             let now = Instant::now();
             
-            if self.should_run("bat", now, 60) {
+            if self.should_run("bat", now) {
                 let tx = self.bat_tx.clone();
                 let notify = self.notify.clone();
                 tokio::spawn(async move {
@@ -117,18 +328,19 @@ impl Scheduler {
                 });
             }
 
-            if self.should_run("cpu", now, 10) {
+            if self.should_run("cpu", now) {
                 let tx = self.cpu_load_tx.clone();
                 let notify = self.notify.clone();
-                let mut monitor = self.cpu_monitor.clone();
+                let monitor = self.cpu_monitor.clone();
                 tokio::spawn(async move {
-                    if let Ok(Ok(val)) = tokio::task::spawn_blocking(move || monitor.update()).await {
+                    let result = tokio::task::spawn_blocking(move || monitor.lock().unwrap().update()).await;
+                    if let Ok(Ok(val)) = result {
                         if tx.send(val).is_ok() { notify.notify_one(); }
                     }
                 });
             }
 
-            if self.should_run("mem", now, 10) {
+            if self.should_run("mem", now) {
                 let tx = self.mem_tx.clone();
                 let notify = self.notify.clone();
                 tokio::spawn(async move {
@@ -139,15 +351,20 @@ impl Scheduler {
             }
             
             // 🔥 **修正 1: 网络任务的所有权问题**
-            if self.should_run("net", now, 2) {
+            if self.should_run("net", now) {
                 let tx = self.net_tx.clone();
                 let notify = self.notify.clone();
-                let mut monitor = self.net_monitor.clone();
+                let monitor = self.net_monitor.clone();
+                let named_iface = self.config.network.interface.clone();
                 tokio::spawn(async move {
                     // 让 spawn_blocking 返回需要的数据，而不是在外部使用被移动的 monitor
                     let result = tokio::task::spawn_blocking(move || {
-                        monitor.update();
-                        (monitor.rx_mbps, monitor.tx_mbps) // 返回元组
+                        let mut guard = monitor.lock().unwrap();
+                        guard.update();
+                        guard
+                            .active(named_iface.as_deref())
+                            .map(|(_, rate)| (rate.rx_mbps, rate.tx_mbps))
+                            .unwrap_or((0.0, 0.0))
                     }).await;
 
                     if let Ok(data) = result {
@@ -158,8 +375,107 @@ impl Scheduler {
                 });
             }
 
+            if self.should_run("cpu_bar", now) {
+                let tx = self.cpu_bar_tx.clone();
+                let notify = self.notify.clone();
+                let monitor = self.cpu_core_monitor.clone();
+                tokio::spawn(async move {
+                    let result = tokio::task::spawn_blocking(move || {
+                        monitor.lock().unwrap().update()
+                    }).await;
+                    if let Ok(bar) = result {
+                        if tx.send(bar).is_ok() { notify.notify_one(); }
+                    }
+                });
+            }
+
+            if self.should_run("load", now) {
+                let tx = self.load_tx.clone();
+                let notify = self.notify.clone();
+                tokio::spawn(async move {
+                    if let Ok(data) = tokio::task::spawn_blocking(LoadAvg::now).await {
+                        if tx.send(data).is_ok() { notify.notify_one(); }
+                    }
+                });
+            }
+
+            if self.should_run("proc", now) {
+                let tx = self.proc_tx.clone();
+                let notify = self.notify.clone();
+                let monitor = self.process_monitor.clone();
+                tokio::spawn(async move {
+                    let result = tokio::task::spawn_blocking(move || {
+                        monitor.lock().unwrap().update()
+                    }).await;
+                    if let Ok(Some(data)) = result {
+                        if tx.send(data).is_ok() { notify.notify_one(); }
+                    }
+                });
+            }
+
+            if let Some(mode) = self.legacy_net_mode {
+                if self.should_run("net_legacy", now) {
+                    let tx = self.legacy_net_tx.clone();
+                    let notify = self.notify.clone();
+                    let sampler = self.legacy_net_sampler.clone();
+                    let config = self.legacy_net_config.clone()
+                        .expect("legacy_net_config is Some whenever legacy_net_mode is");
+                    let iface = self.legacy_net_iface.clone();
+                    tokio::spawn(async move {
+                        let result = tokio::task::spawn_blocking(move || match mode {
+                            LegacyNetMode::Raw => {
+                                let sampler = sampler.expect("sampler is Some whenever mode is Raw");
+                                // A (0, 0) rate is either "no reading yet" (right after
+                                // startup) or a fully idle interval; the two aren't told
+                                // apart, and both fall back to the one-shot reading.
+                                if sampler.rate_bps() == (0.0, 0.0) {
+                                    network::print_network_speed(&config)
+                                } else {
+                                    sampler.print()
+                                }
+                            }
+                            LegacyNetMode::Ewma => network::print_network_speed(&config),
+                            LegacyNetMode::PerIface => {
+                                let text = network::print_network_speed_per_iface(&config, iface.as_deref());
+                                // The selected interface may not exist (typo) or may
+                                // not have been read yet (first tick); don't leave
+                                // {net_legacy} rendering blank in either case.
+                                if text.is_empty() {
+                                    network::print_network_speed(&config)
+                                } else {
+                                    text
+                                }
+                            }
+                        }).await;
+                        if let Ok(text) = result {
+                            if tx.send(text).is_ok() { notify.notify_one(); }
+                        }
+                    });
+                }
+            }
+
+            if self.should_run("temp", now) {
+                let tx = self.cpu_temp_tx.clone();
+                let notify = self.notify.clone();
+                tokio::spawn(async move {
+                    if let Ok(data) = tokio::task::spawn_blocking(CpuTemp::now).await {
+                        if tx.send(data).is_ok() { notify.notify_one(); }
+                    }
+                });
+            }
+
+            if self.should_run("disk", now) {
+                let tx = self.disk_tx.clone();
+                let notify = self.notify.clone();
+                tokio::spawn(async move {
+                    if let Ok(data) = tokio::task::spawn_blocking(DiskInfo::now).await {
+                        if tx.send(data).is_ok() { notify.notify_one(); }
+                    }
+                });
+            }
+
             // 🔥 **修正 2: 时间任务的阻塞问题**
-            if self.should_run("time", now, 60) {
+            if self.should_run("time", now) {
                 let tx = self.time_tx.clone();
                 let notify = self.notify.clone();
                 tokio::spawn(async move {
@@ -174,9 +490,13 @@ impl Scheduler {
         }
     }
 
-    fn should_run(&mut self, key: &'static str, now: Instant, sec: u64) -> bool {
+    fn should_run(&mut self, key: &'static str, now: Instant) -> bool {
+        if !self.config.is_enabled(key) {
+            return false;
+        }
+        let interval = self.config.interval(key);
         let run = self.last_run.get(key)
-            .map_or(true, |&t| now.duration_since(t) >= Duration::from_secs(sec));
+            .map_or(true, |&t| now.duration_since(t) >= Duration::from_secs(interval));
         if run { self.last_run.insert(key, now); }
         run
     }
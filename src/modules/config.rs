@@ -0,0 +1,132 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+/// Top-level `config.toml` schema: which modules run, in which order, how
+/// often, and how the final status line is laid out.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Config {
+    #[serde(default = "default_format")]
+    pub format: String,
+    #[serde(default)]
+    pub modules: ModulesConfig,
+    #[serde(default)]
+    pub intervals: HashMap<String, u64>,
+    #[serde(default)]
+    pub network: NetworkConfig,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ModulesConfig {
+    #[serde(default = "default_enabled")]
+    pub enabled: Vec<String>,
+}
+
+impl Default for ModulesConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_enabled(),
+        }
+    }
+}
+
+/// Interface selection for the `net` module.
+#[derive(Debug, Clone, Deserialize)]
+pub struct NetworkConfig {
+    /// Show this interface's rate regardless of which one is busiest.
+    pub interface: Option<String>,
+    /// When non-empty, only these prefixes are tracked (e.g. `["wl", "en"]`).
+    #[serde(default)]
+    pub include: Vec<String>,
+    #[serde(default = "default_net_exclude")]
+    pub exclude: Vec<String>,
+}
+
+impl Default for NetworkConfig {
+    fn default() -> Self {
+        Self {
+            interface: None,
+            include: Vec::new(),
+            exclude: default_net_exclude(),
+        }
+    }
+}
+
+fn default_net_exclude() -> Vec<String> {
+    ["lo", "docker", "veth", "br"].iter().map(|s| s.to_string()).collect()
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            format: default_format(),
+            modules: ModulesConfig::default(),
+            intervals: default_intervals(),
+            network: NetworkConfig::default(),
+        }
+    }
+}
+
+fn default_enabled() -> Vec<String> {
+    ["mem", "disk", "net", "cpu", "cpu_bar", "temp", "load", "proc", "bat", "time", "net_legacy"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect()
+}
+
+fn default_format() -> String {
+    // `net_legacy` is opt-in via `--legacy-net` (see main.rs's LegacyNetMode)
+    // and off by default, so it's left out of the default format — a user
+    // who opts in adds `{net_legacy}` to their own config.toml `format`.
+    "Mem: {mem} | Disk: {disk} | Net: ↓{net_rx}M/s ↑{net_tx}M/s | CPU: {cpu_bar} {cpu} {temp} | Load: {load} | Top: {proc} | {bat} | {time}"
+        .to_string()
+}
+
+fn default_intervals() -> HashMap<String, u64> {
+    let mut intervals = HashMap::new();
+    intervals.insert("bat".to_string(), 60);
+    intervals.insert("cpu".to_string(), 10);
+    intervals.insert("cpu_bar".to_string(), 2);
+    intervals.insert("mem".to_string(), 10);
+    intervals.insert("net".to_string(), 2);
+    intervals.insert("time".to_string(), 60);
+    intervals.insert("disk".to_string(), 30);
+    intervals.insert("temp".to_string(), 15);
+    intervals.insert("load".to_string(), 5);
+    intervals.insert("proc".to_string(), 3);
+    intervals.insert("net_legacy".to_string(), 2);
+    intervals
+}
+
+impl Config {
+    /// `~/.config/warustatus/config.toml`, the default location when `-c`/`--config` isn't given.
+    pub fn default_path() -> PathBuf {
+        let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
+        PathBuf::from(home).join(".config/warustatus/config.toml")
+    }
+
+    /// Loads and parses `path`, falling back to built-in defaults if the file
+    /// is missing or malformed rather than failing startup.
+    pub fn load(path: &Path) -> Self {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|content| toml::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn is_enabled(&self, module: &str) -> bool {
+        self.modules.enabled.iter().any(|m| m == module)
+    }
+
+    /// Refresh interval in seconds for `module`, falling back to the built-in
+    /// default for modules the user's TOML doesn't mention.
+    pub fn interval(&self, module: &str) -> u64 {
+        self.intervals
+            .get(module)
+            .copied()
+            .or_else(|| default_intervals().get(module).copied())
+            .unwrap_or(10)
+    }
+}
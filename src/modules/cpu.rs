@@ -1,4 +1,8 @@
+use std::collections::{HashSet, VecDeque};
 use std::fs;
+use std::sync::{Mutex, OnceLock};
+
+use notify_rust::{Notification, Urgency};
 
 #[derive(Clone)]
 
@@ -62,6 +66,17 @@ pub struct CpuTemp {
 
 impl CpuTemp {
     pub fn now() -> Self {
+        // hwmon 给出带标签和临界值的真实传感器读数，优先使用；
+        // 只有在没有任何 hwmon 温度输入时才回退到 thermal_zone。
+        if let Some(hottest) = Components::now().hottest() {
+            return Self {
+                celsius: hottest.temp,
+            };
+        }
+        Self::from_thermal_zone()
+    }
+
+    fn from_thermal_zone() -> Self {
         let mut max_temp: f32 = 0.0;
         let mut found_temp = false; // 标记是否找到任何温度读数
         if let Ok(dir) = fs::read_dir("/sys/class/thermal") {
@@ -101,3 +116,244 @@ impl CpuTemp {
         Self { celsius: max_temp }
     }
 }
+
+/// A single `tempN_*` sensor under one hwmon chip.
+#[derive(Clone, Debug)]
+pub struct Component {
+    pub label: String,
+    pub temp: f32,
+    pub max: Option<f32>,
+    pub crit: Option<f32>,
+}
+
+/// All hwmon temperature sensors found under `/sys/class/hwmon/hwmon*`,
+/// mirroring sysinfo's Linux component layer.
+#[derive(Clone, Debug, Default)]
+pub struct Components {
+    pub sensors: Vec<Component>,
+}
+
+impl Components {
+    pub fn now() -> Self {
+        let components = Self {
+            sensors: Self::read_hwmon(),
+        };
+        components.check_thresholds();
+        components
+    }
+
+    fn read_hwmon() -> Vec<Component> {
+        let mut sensors = Vec::new();
+        let Ok(chips) = fs::read_dir("/sys/class/hwmon") else {
+            return sensors;
+        };
+
+        for chip in chips.filter_map(Result::ok) {
+            let chip_path = chip.path();
+            let chip_name = fs::read_to_string(chip_path.join("name"))
+                .map(|s| s.trim().to_string())
+                .unwrap_or_else(|_| "unknown".to_string());
+
+            let Ok(files) = fs::read_dir(&chip_path) else {
+                continue;
+            };
+
+            for file in files.filter_map(Result::ok) {
+                let file_name = file.file_name().to_string_lossy().to_string();
+                if !file_name.starts_with("temp") || !file_name.ends_with("_input") {
+                    continue;
+                }
+                let prefix = file_name.trim_end_matches("_input");
+
+                let Ok(raw) = fs::read_to_string(file.path()) else {
+                    continue;
+                };
+                let Ok(milli) = raw.trim().parse::<i64>() else {
+                    continue;
+                };
+                let temp = milli as f32 / 1000.0;
+
+                let label = fs::read_to_string(chip_path.join(format!("{prefix}_label")))
+                    .map(|s| s.trim().to_string())
+                    .unwrap_or_else(|_| format!("{chip_name} {prefix}"));
+                let max = read_milli_temp(&chip_path.join(format!("{prefix}_max")));
+                let crit = read_milli_temp(&chip_path.join(format!("{prefix}_crit")));
+
+                sensors.push(Component {
+                    label,
+                    temp,
+                    max,
+                    crit,
+                });
+            }
+        }
+
+        sensors
+    }
+
+    /// Notifies only on the not-breached -> breached transition, using
+    /// `breached_sensors` to remember which sensors were already over their
+    /// critical threshold last poll — otherwise a sensor pinned above crit
+    /// keeps firing a Critical notification every single poll.
+    fn check_thresholds(&self) {
+        let mut breached = breached_sensors().lock().unwrap();
+        for sensor in &self.sensors {
+            let Some(crit) = sensor.crit else { continue };
+            let is_breached = sensor.temp >= crit;
+            let was_breached = breached.contains(&sensor.label);
+
+            if is_breached && !was_breached {
+                let _ = Notification::new()
+                    .summary("Temperature Critical")
+                    .body(&format!(
+                        "{}: {:.1}°C (crit {:.1}°C)",
+                        sensor.label, sensor.temp, crit
+                    ))
+                    .urgency(Urgency::Critical)
+                    .show();
+            }
+
+            if is_breached {
+                breached.insert(sensor.label.clone());
+            } else {
+                breached.remove(&sensor.label);
+            }
+        }
+    }
+
+    /// The sensor with the highest current reading, for the status line.
+    pub fn hottest(&self) -> Option<&Component> {
+        self.sensors
+            .iter()
+            .max_by(|a, b| a.temp.partial_cmp(&b.temp).unwrap_or(std::cmp::Ordering::Equal))
+    }
+}
+
+/// Sensor labels currently past their `crit` threshold, persisted across the
+/// otherwise-stateless `Components::now()` calls so `check_thresholds` can
+/// tell a still-breached sensor from a newly-breached one.
+fn breached_sensors() -> &'static Mutex<HashSet<String>> {
+    static BREACHED: OnceLock<Mutex<HashSet<String>>> = OnceLock::new();
+    BREACHED.get_or_init(|| Mutex::new(HashSet::new()))
+}
+
+fn read_milli_temp(path: &std::path::Path) -> Option<f32> {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|s| s.trim().parse::<i64>().ok())
+        .map(|v| v as f32 / 1000.0)
+}
+
+/// Per-core `user+nice+system+irq+softirq` vs `idle+iowait` time, read from
+/// one `cpuN` line of `/proc/stat`.
+#[derive(Clone, Copy, Debug, Default)]
+struct CoreTime {
+    idle: u64,
+    non_idle: u64,
+}
+
+impl CoreTime {
+    fn from_fields(s: &str) -> Option<Self> {
+        let mut parts = s.split_whitespace();
+        let user = parts.next()?.parse::<u64>().ok()?;
+        let nice = parts.next()?.parse::<u64>().ok()?;
+        let system = parts.next()?.parse::<u64>().ok()?;
+        let idle = parts.next()?.parse::<u64>().ok()?;
+        let iowait = parts.next()?.parse::<u64>().ok()?;
+        let irq = parts.next()?.parse::<u64>().ok()?;
+        let softirq = parts.next()?.parse::<u64>().ok()?;
+        Some(Self {
+            idle: idle + iowait,
+            non_idle: user + nice + system + irq + softirq,
+        })
+    }
+
+    fn utilization(&self, prev: CoreTime) -> f64 {
+        let total = self.idle + self.non_idle;
+        let prev_total = prev.idle + prev.non_idle;
+        let total_delta = total.saturating_sub(prev_total);
+        if total_delta == 0 {
+            return 0.0;
+        }
+        let non_idle_delta = self.non_idle.saturating_sub(prev.non_idle);
+        non_idle_delta as f64 / total_delta as f64
+    }
+}
+
+fn read_per_core() -> Vec<CoreTime> {
+    let mut cores = Vec::new();
+    if let Ok(content) = fs::read_to_string("/proc/stat") {
+        for line in content.lines() {
+            // "cpu0", "cpu1", ... but not the aggregate "cpu " line.
+            if line.starts_with("cpu") && !line.starts_with("cpu ") {
+                if let Some(rest) = line.splitn(2, ' ').nth(1) {
+                    if let Some(t) = CoreTime::from_fields(rest) {
+                        cores.push(t);
+                    }
+                }
+            }
+        }
+    }
+    cores
+}
+
+const BOXCHARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Renders a per-core BOXCHARS barchart, smoothing each core's utilization
+/// over a sliding window of recent samples rather than the raw instantaneous
+/// delta.
+#[derive(Clone)]
+pub struct CpuCoreMonitor {
+    prev: Vec<CoreTime>,
+    windows: Vec<VecDeque<f64>>,
+}
+
+impl CpuCoreMonitor {
+    const WINDOW_SIZE: usize = 16;
+
+    pub fn new() -> Self {
+        Self {
+            prev: read_per_core(),
+            windows: Vec::new(),
+        }
+    }
+
+    /// Samples `/proc/stat`, pushes each core's utilization into its ring
+    /// buffer, and returns the barchart of the per-core window averages.
+    pub fn update(&mut self) -> String {
+        let current = read_per_core();
+        if current.len() != self.prev.len() {
+            // Core count changed (hotplug) or this is the first sample; reset.
+            self.prev = current;
+            self.windows = vec![VecDeque::with_capacity(Self::WINDOW_SIZE); self.prev.len()];
+            return String::new();
+        }
+        if self.windows.len() != current.len() {
+            self.windows = vec![VecDeque::with_capacity(Self::WINDOW_SIZE); current.len()];
+        }
+
+        let mut bar = String::with_capacity(current.len());
+        for (i, (new_core, old_core)) in current.iter().zip(self.prev.iter()).enumerate() {
+            let util = new_core.utilization(*old_core);
+
+            let window = &mut self.windows[i];
+            if window.len() == Self::WINDOW_SIZE {
+                window.pop_front();
+            }
+            window.push_back(util);
+            let avg = window.iter().sum::<f64>() / window.len() as f64;
+
+            let idx = ((avg * (BOXCHARS.len() - 1) as f64).round() as usize).min(BOXCHARS.len() - 1);
+            bar.push(BOXCHARS[idx]);
+        }
+
+        self.prev = current;
+        bar
+    }
+}
+
+impl Default for CpuCoreMonitor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,161 @@
+use std::collections::HashSet;
+use std::ffi::CString;
+use std::fs;
+use std::mem::MaybeUninit;
+use std::sync::{Mutex, OnceLock};
+
+use notify_rust::{Notification, Urgency};
+
+/// Filesystem types that never correspond to a real block device and are
+/// skipped when enumerating `/proc/mounts`.
+const PSEUDO_FS: &[&str] = &[
+    "proc", "sysfs", "tmpfs", "devtmpfs", "cgroup", "cgroup2", "overlay", "devpts", "securityfs",
+    "debugfs", "pstore", "bpf", "tracefs", "mqueue", "hugetlbfs", "configfs", "autofs",
+];
+
+/// Usage fraction (0-100) above which a mount fires a Critical notification.
+pub const WARN_THRESHOLD_PERCENT: f64 = 90.0;
+
+#[derive(Clone, Debug)]
+struct MountStat {
+    mount_point: String,
+    total_bytes: u64,
+    avail_bytes: u64,
+}
+
+impl MountStat {
+    fn used_percent(&self) -> f64 {
+        if self.total_bytes == 0 {
+            0.0
+        } else {
+            100.0 * (1.0 - self.avail_bytes as f64 / self.total_bytes as f64)
+        }
+    }
+
+    fn available_gb(&self) -> f64 {
+        self.avail_bytes as f64 / 1_073_741_824.0
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct DiskInfo {
+    mounts: Vec<MountStat>,
+}
+
+impl DiskInfo {
+    pub fn now() -> Self {
+        let info = Self {
+            mounts: Self::read_mounts(),
+        };
+        info.check_thresholds();
+        info
+    }
+
+    fn read_mounts() -> Vec<MountStat> {
+        let mut mounts = Vec::new();
+        let content = match fs::read_to_string("/proc/mounts") {
+            Ok(c) => c,
+            Err(_) => return mounts,
+        };
+
+        for line in content.lines() {
+            let mut fields = line.split_whitespace();
+            let device = match fields.next() {
+                Some(d) => d,
+                None => continue,
+            };
+            let mount_point = match fields.next() {
+                Some(m) => m,
+                None => continue,
+            };
+            let fstype = match fields.next() {
+                Some(f) => f,
+                None => continue,
+            };
+
+            if !device.starts_with('/') || PSEUDO_FS.contains(&fstype) {
+                continue;
+            }
+
+            if let Some(stat) = statvfs_stat(mount_point) {
+                mounts.push(stat);
+            }
+        }
+
+        mounts
+    }
+
+    /// Notifies only on the not-breached -> breached transition, using
+    /// `breached_mounts` to remember which mounts were already over
+    /// threshold last poll — otherwise a mount that stays full keeps firing
+    /// a Critical notification every single poll.
+    fn check_thresholds(&self) {
+        let mut breached = breached_mounts().lock().unwrap();
+        for mount in &self.mounts {
+            let used = mount.used_percent();
+            let is_breached = used >= WARN_THRESHOLD_PERCENT;
+            let was_breached = breached.contains(&mount.mount_point);
+
+            if is_breached && !was_breached {
+                let _ = Notification::new()
+                    .summary("Low Disk Space")
+                    .body(&format!("{} is {:.0}% full", mount.mount_point, used))
+                    .urgency(Urgency::Critical)
+                    .show();
+            }
+
+            if is_breached {
+                breached.insert(mount.mount_point.clone());
+            } else {
+                breached.remove(&mount.mount_point);
+            }
+        }
+    }
+
+    /// The mount chosen for the status line: `/` if it's present, otherwise
+    /// the first real block-backed mount found.
+    pub fn root(&self) -> Option<&str> {
+        self.mounts
+            .iter()
+            .find(|m| m.mount_point == "/")
+            .or_else(|| self.mounts.first())
+            .map(|m| m.mount_point.as_str())
+    }
+
+    pub fn available_gb(&self, mount_point: &str) -> Option<f64> {
+        self.find(mount_point).map(MountStat::available_gb)
+    }
+
+    pub fn used_percent(&self, mount_point: &str) -> Option<f64> {
+        self.find(mount_point).map(MountStat::used_percent)
+    }
+
+    fn find(&self, mount_point: &str) -> Option<&MountStat> {
+        self.mounts.iter().find(|m| m.mount_point == mount_point)
+    }
+}
+
+/// Mount points currently past `WARN_THRESHOLD_PERCENT`, persisted across
+/// the otherwise-stateless `DiskInfo::now()` calls so `check_thresholds` can
+/// tell a still-breached mount from a newly-breached one.
+fn breached_mounts() -> &'static Mutex<HashSet<String>> {
+    static BREACHED: OnceLock<Mutex<HashSet<String>>> = OnceLock::new();
+    BREACHED.get_or_init(|| Mutex::new(HashSet::new()))
+}
+
+fn statvfs_stat(mount_point: &str) -> Option<MountStat> {
+    let c_path = CString::new(mount_point).ok()?;
+    let mut buf = MaybeUninit::<libc::statvfs>::uninit();
+    let ret = unsafe { libc::statvfs(c_path.as_ptr(), buf.as_mut_ptr()) };
+    if ret != 0 {
+        return None;
+    }
+    let buf = unsafe { buf.assume_init() };
+    let frsize = buf.f_frsize as u64;
+
+    Some(MountStat {
+        mount_point: mount_point.to_string(),
+        total_bytes: buf.f_blocks as u64 * frsize,
+        avail_bytes: buf.f_bavail as u64 * frsize,
+    })
+}
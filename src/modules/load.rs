@@ -0,0 +1,45 @@
+use std::fmt;
+use std::fs;
+
+#[derive(Clone, Debug, Default)]
+pub struct LoadAvg {
+    pub one: f64,
+    pub five: f64,
+    pub fifteen: f64,
+    pub running: u32,
+    pub total: u32,
+}
+
+impl LoadAvg {
+    pub fn now() -> Self {
+        let content = fs::read_to_string("/proc/loadavg").unwrap_or_default();
+        let mut fields = content.split_whitespace();
+
+        let one = fields.next().and_then(|s| s.parse().ok()).unwrap_or(0.0);
+        let five = fields.next().and_then(|s| s.parse().ok()).unwrap_or(0.0);
+        let fifteen = fields.next().and_then(|s| s.parse().ok()).unwrap_or(0.0);
+
+        let mut running = 0;
+        let mut total = 0;
+        if let Some(procs) = fields.next() {
+            if let Some((r, t)) = procs.split_once('/') {
+                running = r.parse().unwrap_or(0);
+                total = t.parse().unwrap_or(0);
+            }
+        }
+
+        Self {
+            one,
+            five,
+            fifteen,
+            running,
+            total,
+        }
+    }
+}
+
+impl fmt::Display for LoadAvg {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:.2} {:.2} {:.2}", self.one, self.five, self.fifteen)
+    }
+}
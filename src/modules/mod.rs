@@ -0,0 +1,10 @@
+pub mod battery;
+pub mod config;
+pub mod cpu;
+pub mod disk;
+pub mod ip;
+pub mod load;
+pub mod memory;
+pub mod network;
+pub mod process;
+pub mod time;
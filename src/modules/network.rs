@@ -1,61 +1,126 @@
-use std::{fs, time::Instant};
+use std::{collections::HashMap, fs, time::Instant};
 
-#[derive(Clone)]
+/// Interface name prefixes that are never the "real" uplink, skipped by
+/// default when no explicit include list is configured.
+const DEFAULT_EXCLUDE_PREFIXES: [&str; 4] = ["lo", "docker", "veth", "br"];
 
-pub struct NetworkStats {
+#[derive(Clone, Debug, Default)]
+pub struct IfaceRate {
     pub rx_mbps: f64,
     pub tx_mbps: f64,
     last_rx: u64,
     last_tx: u64,
+}
+
+/// Per-interface rx/tx rates, tracked against each interface's own previous
+/// counters rather than one summed total. The elapsed time is the time since
+/// the previous `update()`, one timestamp shared by all interfaces; there is
+/// no per-interface timestamp.
+#[derive(Clone)]
+pub struct NetworkStats {
+    pub ifaces: HashMap<String, IfaceRate>,
     last_time: Instant,
+    include_prefixes: Vec<String>,
+    exclude_prefixes: Vec<String>,
 }
 
 impl NetworkStats {
     pub fn new() -> Self {
+        Self::with_filters(
+            Vec::new(),
+            DEFAULT_EXCLUDE_PREFIXES.iter().map(|s| s.to_string()).collect(),
+        )
+    }
+
+    /// `include_prefixes` takes priority when non-empty (e.g. only
+    /// `wl*`/`en*`); otherwise every interface not matching
+    /// `exclude_prefixes` is tracked.
+    pub fn with_filters(include_prefixes: Vec<String>, exclude_prefixes: Vec<String>) -> Self {
         Self {
-            rx_mbps: 0.0,
-            tx_mbps: 0.0,
-            last_rx: 0,
-            last_tx: 0,
+            ifaces: HashMap::new(),
             last_time: Instant::now(),
+            include_prefixes,
+            exclude_prefixes,
         }
     }
 
     pub fn update(&mut self) {
-        let (rx, tx) = self.read_counters();
-        let elapsed = self.last_time.elapsed().as_secs_f64();
+        let elapsed = self.last_time.elapsed().as_secs_f64().max(0.1);
 
-        self.rx_mbps = (rx - self.last_rx) as f64 / 1_048_576.0 / elapsed.max(0.1);
-        self.tx_mbps = (tx - self.last_tx) as f64 / 1_048_576.0 / elapsed.max(0.1);
+        for (iface, (rx, tx)) in self.read_counters() {
+            let entry = self.ifaces.entry(iface).or_insert_with(|| IfaceRate {
+                last_rx: rx,
+                last_tx: tx,
+                ..Default::default()
+            });
+            entry.rx_mbps = rx.saturating_sub(entry.last_rx) as f64 / 1_048_576.0 / elapsed;
+            entry.tx_mbps = tx.saturating_sub(entry.last_tx) as f64 / 1_048_576.0 / elapsed;
+            entry.last_rx = rx;
+            entry.last_tx = tx;
+        }
 
-        self.last_rx = rx;
-        self.last_tx = tx;
         self.last_time = Instant::now();
     }
 
-    fn read_counters(&self) -> (u64, u64) {
-        let mut rx = 0;
-        let mut tx = 0;
+    fn read_counters(&self) -> HashMap<String, (u64, u64)> {
+        let mut counters = HashMap::new();
 
         if let Ok(dir) = fs::read_dir("/sys/class/net") {
             for entry in dir.filter_map(Result::ok) {
                 let path = entry.path();
-                if path.is_dir() {
-                    let iface = path.file_name().unwrap().to_string_lossy();
-                    if iface.starts_with("lo") {
-                        continue;
-                    }
-
-                    if let Ok(rx_bytes) = fs::read_to_string(path.join("statistics/rx_bytes")) {
-                        rx += rx_bytes.trim().parse::<u64>().unwrap_or(0);
-                    }
-                    if let Ok(tx_bytes) = fs::read_to_string(path.join("statistics/tx_bytes")) {
-                        tx += tx_bytes.trim().parse::<u64>().unwrap_or(0);
-                    }
+                let iface = path.file_name().unwrap().to_string_lossy().to_string();
+                if !self.is_tracked(&iface) {
+                    continue;
                 }
+
+                let rx = fs::read_to_string(path.join("statistics/rx_bytes"))
+                    .ok()
+                    .and_then(|s| s.trim().parse().ok())
+                    .unwrap_or(0);
+                let tx = fs::read_to_string(path.join("statistics/tx_bytes"))
+                    .ok()
+                    .and_then(|s| s.trim().parse().ok())
+                    .unwrap_or(0);
+                counters.insert(iface, (rx, tx));
+            }
+        }
+
+        counters
+    }
+
+    fn is_tracked(&self, iface: &str) -> bool {
+        if !self.include_prefixes.is_empty() {
+            return self.include_prefixes.iter().any(|p| iface.starts_with(p.as_str()));
+        }
+        !self.exclude_prefixes.iter().any(|p| iface.starts_with(p.as_str()))
+    }
+
+    fn is_operstate_up(iface: &str) -> bool {
+        fs::read_to_string(format!("/sys/class/net/{iface}/operstate"))
+            .map(|s| s.trim() == "up")
+            .unwrap_or(false)
+    }
+
+    /// The interface the status line should show: `named` if it's given and
+    /// currently tracked, otherwise the busiest "up" interface carrying
+    /// nonzero traffic.
+    pub fn active<'a>(&'a self, named: Option<&'a str>) -> Option<(&'a str, &'a IfaceRate)> {
+        if let Some(name) = named {
+            if let Some(rate) = self.ifaces.get(name) {
+                return Some((name, rate));
             }
         }
 
-        (rx, tx)
+        self.ifaces
+            .iter()
+            .filter(|(iface, rate)| {
+                (rate.rx_mbps > 0.0 || rate.tx_mbps > 0.0) && Self::is_operstate_up(iface)
+            })
+            .max_by(|a, b| {
+                (a.1.rx_mbps + a.1.tx_mbps)
+                    .partial_cmp(&(b.1.rx_mbps + b.1.tx_mbps))
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            })
+            .map(|(iface, rate)| (iface.as_str(), rate))
     }
 }
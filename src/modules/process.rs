@@ -0,0 +1,107 @@
+use std::collections::HashMap;
+use std::fs;
+
+/// The process using the most CPU since the last sample, with its resident
+/// memory (`rss_mb`) reported alongside; it is not chosen by memory use.
+#[derive(Clone, Debug, Default)]
+pub struct TopProcess {
+    pub pid: i32,
+    pub name: String,
+    pub cpu_percent: f64,
+    pub rss_mb: f64,
+}
+
+/// Samples `/proc/[pid]/stat` on each `update()`, keeping the previous
+/// sample's CPU ticks per pid so it can compute a delta against the total
+/// `/proc/stat` delta over the interval.
+#[derive(Clone, Default)]
+pub struct ProcessMonitor {
+    prev_ticks: HashMap<i32, u64>,
+    prev_total: u64,
+}
+
+impl ProcessMonitor {
+    pub fn new() -> Self {
+        Self {
+            prev_ticks: HashMap::new(),
+            prev_total: read_total_ticks().unwrap_or(0),
+        }
+    }
+
+    pub fn update(&mut self) -> Option<TopProcess> {
+        let total = read_total_ticks()?;
+        let total_delta = total.saturating_sub(self.prev_total);
+
+        let mut current_ticks = HashMap::new();
+        let mut top: Option<(i32, String, u64)> = None;
+
+        let entries = fs::read_dir("/proc").ok()?;
+        for entry in entries.filter_map(Result::ok) {
+            let Ok(pid) = entry.file_name().to_string_lossy().parse::<i32>() else {
+                continue;
+            };
+            let Some((name, ticks)) = read_proc_ticks(pid) else {
+                // The process may have exited between listing /proc and reading its stat file.
+                continue;
+            };
+
+            let delta = ticks.saturating_sub(self.prev_ticks.get(&pid).copied().unwrap_or(ticks));
+            current_ticks.insert(pid, ticks);
+
+            let is_new_max = match &top {
+                Some((_, _, best_delta)) => delta > *best_delta,
+                None => true,
+            };
+            if is_new_max {
+                top = Some((pid, name, delta));
+            }
+        }
+
+        self.prev_ticks = current_ticks;
+        self.prev_total = total;
+
+        top.map(|(pid, name, delta)| TopProcess {
+            pid,
+            name,
+            cpu_percent: if total_delta == 0 {
+                0.0
+            } else {
+                100.0 * delta as f64 / total_delta as f64
+            },
+            rss_mb: read_rss_mb(pid).unwrap_or(0.0),
+        })
+    }
+}
+
+fn read_total_ticks() -> Option<u64> {
+    let content = fs::read_to_string("/proc/stat").ok()?;
+    let line = content.lines().next()?;
+    Some(
+        line.split_whitespace()
+            .skip(1)
+            .filter_map(|s| s.parse::<u64>().ok())
+            .sum(),
+    )
+}
+
+/// Reads `comm` (field 2) and `utime+stime` (fields 14/15) from
+/// `/proc/[pid]/stat`, tolerating spaces/parens inside `comm`.
+fn read_proc_ticks(pid: i32) -> Option<(String, u64)> {
+    let stat = fs::read_to_string(format!("/proc/{pid}/stat")).ok()?;
+    let open = stat.find('(')?;
+    let close = stat.rfind(')')?;
+    let name = stat[open + 1..close].to_string();
+
+    let rest: Vec<&str> = stat[close + 1..].split_whitespace().collect();
+    // `rest[0]` is field 3 (state); utime/stime are fields 14/15.
+    let utime: u64 = rest.get(11)?.parse().ok()?;
+    let stime: u64 = rest.get(12)?.parse().ok()?;
+    Some((name, utime + stime))
+}
+
+fn read_rss_mb(pid: i32) -> Option<f64> {
+    let statm = fs::read_to_string(format!("/proc/{pid}/statm")).ok()?;
+    let rss_pages: u64 = statm.split_whitespace().nth(1)?.parse().ok()?;
+    let page_size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) } as u64;
+    Some((rss_pages * page_size) as f64 / 1_048_576.0)
+}
@@ -0,0 +1,164 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+/// Display unit for a throughput figure; `Auto` picks the largest unit that
+/// keeps the value above 1.0.
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Unit {
+    B,
+    Kb,
+    Mb,
+    Gb,
+    Auto,
+}
+
+/// Interface selection, units, state file location, and precision for the
+/// `network` module, loaded from a TOML file under `$XDG_CONFIG_HOME`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct NetConfig {
+    /// Explicit interface names/prefixes to track; empty means "all".
+    #[serde(default)]
+    pub include: Vec<String>,
+    /// Interface names/prefixes to always skip, even if `include` matches.
+    #[serde(default)]
+    pub exclude: Vec<String>,
+    #[serde(default = "default_unit")]
+    pub unit: Unit,
+    #[serde(default = "default_state_file")]
+    pub state_file: PathBuf,
+    /// State file for [`network::print_network_speed_per_iface`], kept
+    /// separate from `state_file` since it holds a serde-serialized map
+    /// rather than the summed-total whitespace format.
+    #[serde(default = "default_per_iface_state_file")]
+    pub per_iface_state_file: PathBuf,
+    #[serde(default = "default_precision")]
+    pub precision: usize,
+    /// EWMA smoothing factor in `(0, 1]`: higher weighs recent samples more.
+    #[serde(default = "default_alpha")]
+    pub alpha: f64,
+    /// Wrap the rx/tx figures in ANSI color escapes chosen by
+    /// `color_green_below_mbps`/`color_yellow_below_mbps`. Off by default so
+    /// bars that don't support ANSI/Pango markup still get plain text.
+    #[serde(default)]
+    pub color: bool,
+    #[serde(default = "default_color_green_below_mbps")]
+    pub color_green_below_mbps: f64,
+    #[serde(default = "default_color_yellow_below_mbps")]
+    pub color_yellow_below_mbps: f64,
+}
+
+fn default_unit() -> Unit {
+    Unit::Auto
+}
+
+fn default_state_file() -> PathBuf {
+    PathBuf::from("/dev/shm/netlog")
+}
+
+fn default_per_iface_state_file() -> PathBuf {
+    PathBuf::from("/dev/shm/netlog.ifaces")
+}
+
+fn default_precision() -> usize {
+    2
+}
+
+fn default_alpha() -> f64 {
+    0.3
+}
+
+fn default_color_green_below_mbps() -> f64 {
+    1.0
+}
+
+fn default_color_yellow_below_mbps() -> f64 {
+    10.0
+}
+
+impl Default for NetConfig {
+    fn default() -> Self {
+        Self {
+            // Matches the previous hardcoded behavior: only ethernet/wifi-looking names.
+            include: vec!["e".to_string(), "w".to_string()],
+            exclude: Vec::new(),
+            unit: default_unit(),
+            state_file: default_state_file(),
+            per_iface_state_file: default_per_iface_state_file(),
+            precision: default_precision(),
+            alpha: default_alpha(),
+            color: false,
+            color_green_below_mbps: default_color_green_below_mbps(),
+            color_yellow_below_mbps: default_color_yellow_below_mbps(),
+        }
+    }
+}
+
+impl NetConfig {
+    pub fn default_path() -> PathBuf {
+        let config_home = std::env::var("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .unwrap_or_else(|_| {
+                let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
+                PathBuf::from(home).join(".config")
+            });
+        config_home.join("warustatus/network.toml")
+    }
+
+    pub fn load(path: &Path) -> Self {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|content| toml::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn is_tracked(&self, iface: &str) -> bool {
+        let included = self.include.is_empty() || self.include.iter().any(|p| iface.starts_with(p.as_str()));
+        included && !self.exclude.iter().any(|p| iface.starts_with(p.as_str()))
+    }
+
+    /// Scales `bytes_per_sec` to the configured unit and formats it with the
+    /// configured decimal precision.
+    pub fn format_rate(&self, bytes_per_sec: f64) -> String {
+        let (value, suffix) = match self.unit {
+            Unit::B => (bytes_per_sec, "B/s"),
+            Unit::Kb => (bytes_per_sec / 1e3, "KB/s"),
+            Unit::Mb => (bytes_per_sec / 1e6, "MB/s"),
+            Unit::Gb => (bytes_per_sec / 1e9, "GB/s"),
+            Unit::Auto => {
+                if bytes_per_sec >= 1e9 {
+                    (bytes_per_sec / 1e9, "GB/s")
+                } else if bytes_per_sec >= 1e6 {
+                    (bytes_per_sec / 1e6, "MB/s")
+                } else if bytes_per_sec >= 1e3 {
+                    (bytes_per_sec / 1e3, "KB/s")
+                } else {
+                    (bytes_per_sec, "B/s")
+                }
+            }
+        };
+        format!("{:.*} {}", self.precision, value, suffix)
+    }
+
+    /// [`format_rate`](Self::format_rate), wrapped in an ANSI color escape
+    /// picked by `bytes_per_sec` against the green/yellow thresholds (red
+    /// above yellow). A no-op when `color` is off, so plain-text bars are
+    /// unaffected.
+    pub fn format_rate_colored(&self, bytes_per_sec: f64) -> String {
+        let text = self.format_rate(bytes_per_sec);
+        if !self.color {
+            return text;
+        }
+        let mbps = bytes_per_sec / 1e6;
+        let code = if mbps < self.color_green_below_mbps {
+            "32"
+        } else if mbps < self.color_yellow_below_mbps {
+            "33"
+        } else {
+            "31"
+        };
+        format!("\x1b[{}m{}\x1b[0m", code, text)
+    }
+}
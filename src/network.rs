@@ -1,19 +1,122 @@
+// `network.rs` is a file module, so `mod i3bar;` would normally resolve to
+// `src/network/i3bar.rs`; the file actually lives at the crate root (a
+// sibling of `network.rs` itself), hence the explicit `#[path]`.
+#[path = "i3bar.rs"]
+mod i3bar;
+// Same issue as mod i3bar above: src/net_config.rs lives at the crate root,
+// not under src/network/.
+#[path = "net_config.rs"]
+mod net_config;
+
+use std::collections::HashMap;
 use std::fs;
 use std::path::Path;
+use std::sync::{Arc, RwLock};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
-pub fn print_network_speed() -> String {
-    let logfile = "/dev/shm/netlog";
+use serde::{Deserialize, Serialize};
 
-    // 如果日志文件不存在，则创建
-    if !Path::new(logfile).exists() {
-        let _ = fs::write(logfile, "0 0");
-    }
+pub use i3bar::Block;
+pub use net_config::NetConfig;
+
+/// Persisted across invocations in the state file: `rxprev txprev
+/// t_prev_millis rx_ewma tx_ewma`.
+#[derive(Default)]
+struct State {
+    rx_prev: u64,
+    tx_prev: u64,
+    t_prev_millis: u128,
+    rx_ewma: f64,
+    tx_ewma: f64,
+}
+
+fn now_millis() -> u128 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis())
+        .unwrap_or(0)
+}
+
+/// Returns the previous state and whether this is a first run, i.e. the
+/// state file is missing/malformed and so unusable (`true`), in which case
+/// the EWMA should be seeded with the instantaneous rate rather than carry a
+/// stale average.
+fn load_state(path: &Path) -> (State, bool) {
+    let content = match fs::read_to_string(path) {
+        Ok(c) => c,
+        Err(_) => return (State::default(), true),
+    };
 
-    let content = fs::read_to_string(logfile).unwrap_or_else(|_| "0 0".to_string());
     let mut parts = content.split_whitespace();
-    let rxprev: u64 = parts.next().unwrap_or("0").parse().unwrap_or(0);
-    let txprev: u64 = parts.next().unwrap_or("0").parse().unwrap_or(0);
+    let parsed = (|| {
+        Some(State {
+            rx_prev: parts.next()?.parse().ok()?,
+            tx_prev: parts.next()?.parse().ok()?,
+            t_prev_millis: parts.next()?.parse().ok()?,
+            rx_ewma: parts.next()?.parse().ok()?,
+            tx_ewma: parts.next()?.parse().ok()?,
+        })
+    })();
+
+    match parsed {
+        Some(state) => (state, false),
+        None => (State::default(), true),
+    }
+}
+
+fn save_state(path: &Path, state: &State) {
+    let _ = fs::write(
+        path,
+        format!(
+            "{} {} {} {} {}",
+            state.rx_prev, state.tx_prev, state.t_prev_millis, state.rx_ewma, state.tx_ewma
+        ),
+    );
+}
+
+pub fn print_network_speed(config: &NetConfig) -> String {
+    let logfile = &config.state_file;
+    let (prev, is_first_run) = load_state(logfile);
+
+    let (rxcurrent, txcurrent) = read_counters(config);
+    let now = now_millis();
+
+    // Guard against two calls landing in the same millisecond.
+    let elapsed_secs = ((now.saturating_sub(prev.t_prev_millis)) as f64 / 1000.0).max(f64::EPSILON);
+    let counters_reset = rxcurrent < prev.rx_prev || txcurrent < prev.tx_prev;
+
+    let inst_rx = rxcurrent.saturating_sub(prev.rx_prev) as f64 / elapsed_secs;
+    let inst_tx = txcurrent.saturating_sub(prev.tx_prev) as f64 / elapsed_secs;
+
+    let (rx_ewma, tx_ewma) = if is_first_run || counters_reset {
+        (inst_rx, inst_tx)
+    } else {
+        (
+            config.alpha * inst_rx + (1.0 - config.alpha) * prev.rx_ewma,
+            config.alpha * inst_tx + (1.0 - config.alpha) * prev.tx_ewma,
+        )
+    };
+
+    save_state(
+        logfile,
+        &State {
+            rx_prev: rxcurrent,
+            tx_prev: txcurrent,
+            t_prev_millis: now,
+            rx_ewma,
+            tx_ewma,
+        },
+    );
+
+    format!(
+        "{} ↓ {} ↑",
+        config.format_rate_colored(rx_ewma),
+        config.format_rate_colored(tx_ewma)
+    )
+}
 
+fn read_counters(config: &NetConfig) -> (u64, u64) {
     let mut rxcurrent = 0u64;
     let mut txcurrent = 0u64;
 
@@ -22,31 +125,259 @@ pub fn print_network_speed() -> String {
         for entry in entries.flatten() {
             // 获取接口名称，确保为 UTF-8 字符串
             if let Ok(iface) = entry.file_name().into_string() {
-                // 仅处理名称以 'e' 或 'w' 开头的接口（例如 ethernet 或 wifi）
-                if iface.starts_with('e') || iface.starts_with('w') {
-                    let rx_path = entry.path().join("statistics/rx_bytes");
-                    let tx_path = entry.path().join("statistics/tx_bytes");
-                    if Path::new(&rx_path).exists() {
-                        if let Ok(rx_str) = fs::read_to_string(&rx_path) {
-                            rxcurrent += rx_str.trim().parse::<u64>().unwrap_or(0);
-                        }
+                if !config.is_tracked(&iface) {
+                    continue;
+                }
+
+                let rx_path = entry.path().join("statistics/rx_bytes");
+                let tx_path = entry.path().join("statistics/tx_bytes");
+                if Path::new(&rx_path).exists() {
+                    if let Ok(rx_str) = fs::read_to_string(&rx_path) {
+                        rxcurrent += rx_str.trim().parse::<u64>().unwrap_or(0);
                     }
-                    if Path::new(&tx_path).exists() {
-                        if let Ok(tx_str) = fs::read_to_string(&tx_path) {
-                            txcurrent += tx_str.trim().parse::<u64>().unwrap_or(0);
-                        }
+                }
+                if Path::new(&tx_path).exists() {
+                    if let Ok(tx_str) = fs::read_to_string(&tx_path) {
+                        txcurrent += tx_str.trim().parse::<u64>().unwrap_or(0);
                     }
                 }
             }
         }
     }
 
-    let diff_rx = rxcurrent.saturating_sub(rxprev) as f64;
-    let diff_tx = txcurrent.saturating_sub(txprev) as f64;
-    let rx_mb = diff_rx / 1e6;
-    let tx_mb = diff_tx / 1e6;
-    let _ = fs::write(logfile, format!("{} {}", rxcurrent, txcurrent));
+    (rxcurrent, txcurrent)
+}
+
+/// Per-interface counters, keyed by interface name, instead of summed across
+/// every tracked interface.
+fn read_counters_per_iface(config: &NetConfig) -> HashMap<String, (u64, u64)> {
+    let mut ifaces = HashMap::new();
+
+    if let Ok(entries) = fs::read_dir("/sys/class/net") {
+        for entry in entries.flatten() {
+            if let Ok(iface) = entry.file_name().into_string() {
+                if !config.is_tracked(&iface) {
+                    continue;
+                }
+
+                let rx_path = entry.path().join("statistics/rx_bytes");
+                let tx_path = entry.path().join("statistics/tx_bytes");
+                let rx = fs::read_to_string(&rx_path)
+                    .ok()
+                    .and_then(|s| s.trim().parse::<u64>().ok())
+                    .unwrap_or(0);
+                let tx = fs::read_to_string(&tx_path)
+                    .ok()
+                    .and_then(|s| s.trim().parse::<u64>().ok())
+                    .unwrap_or(0);
+                ifaces.insert(iface, (rx, tx));
+            }
+        }
+    }
+
+    ifaces
+}
+
+/// EWMA-smoothed rate state for a single interface, persisted as part of
+/// [`PerIfaceState`].
+#[derive(Serialize, Deserialize, Clone, Copy, Default)]
+struct IfaceState {
+    rx_prev: u64,
+    tx_prev: u64,
+    rx_ewma: f64,
+    tx_ewma: f64,
+}
+
+/// Persisted across invocations of [`print_network_speed_per_iface`] as JSON,
+/// keyed by interface name rather than the single-total whitespace format.
+#[derive(Serialize, Deserialize, Default)]
+struct PerIfaceState {
+    t_prev_millis: u128,
+    ifaces: HashMap<String, IfaceState>,
+}
+
+fn load_per_iface_state(path: &Path) -> PerIfaceState {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save_per_iface_state(path: &Path, state: &PerIfaceState) {
+    if let Ok(json) = serde_json::to_string(state) {
+        let _ = fs::write(path, json);
+    }
+}
+
+/// Per-interface equivalent of [`print_network_speed`]: one EWMA-smoothed
+/// rate per tracked interface instead of a single sum, so a busy VPN link
+/// doesn't hide an idle ethernet link or vice versa.
+pub fn network_rates_per_iface(config: &NetConfig) -> HashMap<String, (f64, f64)> {
+    let logfile = &config.per_iface_state_file;
+    let mut prev = load_per_iface_state(logfile);
+
+    let current = read_counters_per_iface(config);
+    let now = now_millis();
+    let elapsed_secs = ((now.saturating_sub(prev.t_prev_millis)) as f64 / 1000.0).max(f64::EPSILON);
+
+    let mut rates = HashMap::new();
+    let mut next_ifaces = HashMap::new();
+
+    for (iface, &(rx, tx)) in &current {
+        let prev_state = prev.ifaces.remove(iface);
+        let (inst_rx, inst_tx, is_first_run, counters_reset) = match prev_state {
+            Some(p) => (
+                rx.saturating_sub(p.rx_prev) as f64 / elapsed_secs,
+                tx.saturating_sub(p.tx_prev) as f64 / elapsed_secs,
+                false,
+                rx < p.rx_prev || tx < p.tx_prev,
+            ),
+            None => (0.0, 0.0, true, false),
+        };
+
+        let (rx_ewma, tx_ewma) = if is_first_run || counters_reset {
+            (inst_rx, inst_tx)
+        } else {
+            let p = prev_state.unwrap();
+            (
+                config.alpha * inst_rx + (1.0 - config.alpha) * p.rx_ewma,
+                config.alpha * inst_tx + (1.0 - config.alpha) * p.tx_ewma,
+            )
+        };
+
+        rates.insert(iface.clone(), (rx_ewma, tx_ewma));
+        next_ifaces.insert(
+            iface.clone(),
+            IfaceState {
+                rx_prev: rx,
+                tx_prev: tx,
+                rx_ewma,
+                tx_ewma,
+            },
+        );
+    }
+
+    save_per_iface_state(
+        logfile,
+        &PerIfaceState {
+            t_prev_millis: now,
+            ifaces: next_ifaces,
+        },
+    );
+
+    rates
+}
+
+/// Same as [`network_rates_per_iface`], formatted as `"iface: rx ↓ tx ↑"` entries
+/// joined by spaces (or a single interface's figures if `only` is given).
+pub fn print_network_speed_per_iface(config: &NetConfig, only: Option<&str>) -> String {
+    let mut rates: Vec<_> = network_rates_per_iface(config).into_iter().collect();
+    rates.sort_by(|a, b| a.0.cmp(&b.0));
+
+    rates
+        .into_iter()
+        .filter(|(iface, _)| only.map_or(true, |want| iface == want))
+        .map(|(iface, (rx, tx))| {
+            format!(
+                "{}: {} ↓ {} ↑",
+                iface,
+                config.format_rate_colored(rx),
+                config.format_rate_colored(tx)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[derive(Clone, Copy, Default)]
+struct SampleRate {
+    rx_bps: f64,
+    tx_bps: f64,
+}
+
+/// Samples `/sys/class/net/*/statistics/{rx,tx}_bytes` on a fixed interval
+/// from a background thread, so the reported rate is bytes/sec regardless of
+/// how often the foreground formatter polls it. Cheap to clone: the latest
+/// reading lives behind the shared `Arc`, so every clone sees the same
+/// background thread's updates rather than a frozen snapshot.
+#[derive(Clone)]
+pub struct NetSampler {
+    latest: Arc<RwLock<SampleRate>>,
+    config: NetConfig,
+}
+
+impl NetSampler {
+    pub fn start(config: NetConfig, interval: Duration) -> Self {
+        let latest = Arc::new(RwLock::new(SampleRate::default()));
+        let writer = latest.clone();
+        let sampler_config = config.clone();
+
+        thread::spawn(move || {
+            let mut prev = read_counters(&sampler_config);
+            let mut prev_time = Instant::now();
+
+            loop {
+                thread::sleep(interval);
+
+                let current = read_counters(&sampler_config);
+                let elapsed = prev_time.elapsed().as_secs_f64().max(0.001);
+                let rate = SampleRate {
+                    rx_bps: current.0.saturating_sub(prev.0) as f64 / elapsed,
+                    tx_bps: current.1.saturating_sub(prev.1) as f64 / elapsed,
+                };
+
+                if let Ok(mut guard) = writer.write() {
+                    *guard = rate;
+                }
+
+                prev = current;
+                prev_time = Instant::now();
+            }
+        });
+
+        Self { latest, config }
+    }
+
+    /// Most recent rate in bytes/sec, unaffected by the caller's own poll cadence.
+    pub fn rate_bps(&self) -> (f64, f64) {
+        let rate = self.latest.read().map(|guard| *guard).unwrap_or_default();
+        (rate.rx_bps, rate.tx_bps)
+    }
+
+    /// Same rate, formatted per the sampler's [`NetConfig`].
+    pub fn print(&self) -> String {
+        let (rx_bps, tx_bps) = self.rate_bps();
+        format!(
+            "{} ↓ {} ↑",
+            self.config.format_rate_colored(rx_bps),
+            self.config.format_rate_colored(tx_bps)
+        )
+    }
 
-    format!("{:.2} ↓ {:.2} ↑", rx_mb, tx_mb)
+    /// Same rate as an i3bar/swaybar [`Block`] instead of a plain string.
+    pub fn block(&self) -> Block {
+        Block::new("network", "rx_tx", self.print())
+    }
 }
 
+/// [`print_network_speed`], wrapped as an i3bar/swaybar [`Block`] so the
+/// crate can be used directly as a bar backend rather than only through
+/// shell string substitution. Narrowed to one interface via
+/// [`print_network_speed_per_iface`] when `iface` is given, so `--legacy-i3bar`
+/// shares the same interface-selection semantics as the scheduler-embedded
+/// `--legacy-net per-iface` mode instead of only ever showing the summed total.
+pub fn network_block(config: &NetConfig, iface: Option<&str>) -> Block {
+    let body = match iface {
+        Some(name) => print_network_speed_per_iface(config, Some(name)),
+        None => print_network_speed(config),
+    };
+    Block::new("network", "rx_tx", body)
+}
+
+/// Runs the legacy network module as a standalone i3bar/swaybar JSON stream
+/// (`{"version":1}` header, then one `[network_block(config, iface)]` array
+/// per `interval`) instead of feeding `main.rs`'s `Scheduler`. Never returns;
+/// intended for `--legacy-i3bar`, where this module drives the whole process.
+pub fn run_i3bar_stream(config: NetConfig, iface: Option<String>, interval: Duration) -> ! {
+    i3bar::stream(interval, move || vec![network_block(&config, iface.as_deref())])
+}